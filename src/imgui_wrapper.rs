@@ -0,0 +1,166 @@
+use ggez::graphics;
+use ggez::Context;
+
+use gfx_core::{handle::RenderTargetView, memory::Typed};
+use gfx_device_gl;
+
+use imgui::*;
+use imgui_gfx_renderer::*;
+
+use std::time::Instant;
+
+/// Runtime-tunable simulation parameters exposed through the debug overlay.
+///
+/// These mirror values that used to be compile-time constants or hardcoded
+/// literals in `update`; the sliders below write straight into a `Tuning`
+/// owned by `GameState`, so dragging them changes the live simulation.
+pub struct Tuning {
+    pub gravity: f32,
+    pub damping: f32,
+    pub spawn_burst: i32,
+    pub batched_drawing: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+struct MouseState {
+    pos: (i32, i32),
+    pressed: (bool, bool, bool),
+    wheel: f32,
+}
+
+pub struct ImGuiWrapper {
+    pub imgui: imgui::Context,
+    pub renderer: Renderer<gfx_core::format::Rgba8, gfx_device_gl::Resources>,
+    last_frame: Instant,
+    mouse_state: MouseState,
+}
+
+impl ImGuiWrapper {
+    pub fn new(ctx: &mut Context) -> Self {
+        // Create the imgui object.
+        let mut imgui = imgui::Context::create();
+        let (factory, gfx_device, _, _, _) = graphics::gfx_objects(ctx);
+
+        // Shaders.
+        let shaders = {
+            let version = gfx_device.get_info().shading_language;
+            if version.is_embedded {
+                if version.major >= 3 {
+                    Shaders::GlSlEs300
+                } else {
+                    Shaders::GlSlEs100
+                }
+            } else if version.major >= 4 {
+                Shaders::GlSl400
+            } else if version.major >= 3 {
+                Shaders::GlSl130
+            } else {
+                Shaders::GlSl110
+            }
+        };
+
+        // Renderer.
+        let renderer = Renderer::init(&mut imgui, &mut *factory, shaders).unwrap();
+
+        // Create instance.
+        Self {
+            imgui,
+            renderer,
+            last_frame: Instant::now(),
+            mouse_state: MouseState::default(),
+        }
+    }
+
+    /// Builds and renders the debug panel for this frame. The panel's widgets
+    /// write directly into `tuning`, while `fps_history` feeds the rolling plot
+    /// and `bunny_count` the live readout.
+    pub fn render(
+        &mut self,
+        ctx: &mut Context,
+        hidpi_factor: f32,
+        tuning: &mut Tuning,
+        fps_history: &[f32],
+        bunny_count: usize,
+    ) {
+        // Update mouse.
+        self.update_mouse();
+
+        // Create new frame.
+        let now = Instant::now();
+        let delta = now - self.last_frame;
+        let delta_s = delta.as_secs() as f32 + delta.subsec_nanos() as f32 / 1_000_000_000.0;
+        self.last_frame = now;
+
+        let (draw_width, draw_height) = graphics::drawable_size(ctx);
+        self.imgui.io_mut().display_size = [draw_width, draw_height];
+        self.imgui.io_mut().display_framebuffer_scale = [hidpi_factor, hidpi_factor];
+        self.imgui.io_mut().delta_time = delta_s;
+
+        let ui = self.imgui.frame();
+
+        // Build the tuning window.
+        {
+            let window = imgui::Window::new(im_str!("Tuning"));
+            window
+                .size([300.0, 250.0], imgui::Condition::FirstUseEver)
+                .position([10.0, 10.0], imgui::Condition::FirstUseEver)
+                .build(&ui, || {
+                    ui.text(im_str!("Bunnies: {}", bunny_count));
+                    ui.plot_lines(im_str!("FPS"), fps_history)
+                        .scale_min(0.0)
+                        .graph_size([0.0, 60.0])
+                        .build();
+                    ui.separator();
+                    imgui::Slider::new(im_str!("Gravity"), 0.0..=5.0)
+                        .build(&ui, &mut tuning.gravity);
+                    imgui::Slider::new(im_str!("Bounce damping"), -1.0..=0.0)
+                        .build(&ui, &mut tuning.damping);
+                    imgui::Slider::new(im_str!("Spawn burst"), 1..=1000)
+                        .build(&ui, &mut tuning.spawn_burst);
+                    ui.checkbox(im_str!("Batched drawing"), &mut tuning.batched_drawing);
+                });
+        }
+
+        // Render.
+        let (factory, _, encoder, _, render_target) = graphics::gfx_objects(ctx);
+        let draw_data = ui.render();
+        self.renderer
+            .render(
+                &mut *factory,
+                encoder,
+                &mut RenderTargetView::new(render_target),
+                draw_data,
+            )
+            .unwrap();
+    }
+
+    fn update_mouse(&mut self) {
+        self.imgui.io_mut().mouse_pos =
+            [self.mouse_state.pos.0 as f32, self.mouse_state.pos.1 as f32];
+
+        self.imgui.io_mut().mouse_down = [
+            self.mouse_state.pressed.0,
+            self.mouse_state.pressed.1,
+            self.mouse_state.pressed.2,
+            false,
+            false,
+        ];
+
+        self.imgui.io_mut().mouse_wheel = self.mouse_state.wheel;
+        self.mouse_state.wheel = 0.0;
+    }
+
+    pub fn update_mouse_pos(&mut self, x: f32, y: f32) {
+        self.mouse_state.pos = (x as i32, y as i32);
+    }
+
+    pub fn update_mouse_down(&mut self, pressed: (bool, bool, bool)) {
+        self.mouse_state.pressed = pressed;
+    }
+
+    /// Whether imgui currently wants the pointer, so the game can ignore clicks
+    /// that land on the overlay.
+    pub fn wants_mouse(&self) -> bool {
+        self.imgui.io().want_capture_mouse
+    }
+}
@@ -3,16 +3,23 @@
 /// Based on the bunnymark example from [`tetra`](https://crates.io/crates/tetra)
 /// which is based on https://github.com/openfl/openfl-samples/tree/master/demos/BunnyMark
 /// Original BunnyMark (and sprite) by Iain Lobb
+mod imgui_wrapper;
+
+use std::collections::HashMap;
 use std::env;
 use std::path;
 
+mod imgui_wrapper;
+use imgui_wrapper::{ImGuiWrapper, Tuning};
+
 use nalgebra as na;
 use rand::rngs::ThreadRng;
 use rand::{self, Rng};
 
 use conf::WindowSetup;
-use event::{quit, KeyCode, KeyMods};
-use ggez::graphics::{spritebatch::SpriteBatch, Color, Image};
+use event::winit_event::TouchPhase;
+use event::{quit, Axis, Button, GamepadId, KeyCode, KeyMods};
+use ggez::graphics::{spritebatch::SpriteBatch, BlendMode, Canvas, Color, DrawParam, Image};
 use ggez::Context;
 use ggez::*;
 use input::mouse::position;
@@ -22,7 +29,117 @@ use input::mouse::position;
 const INITIAL_BUNNIES: usize = 100;
 const WIDTH: u16 = 1280;
 const HEIGHT: u16 = 720;
-const GRAVITY: f32 = 0.5;
+// The simulation space is independent of the window and can be much larger; the
+// camera (`Frame`) scrolls the viewport across it.
+const WORLD_WIDTH: f32 = 4000.0;
+const WORLD_HEIGHT: f32 = 3000.0;
+// Pixels per tick the viewport pans when driven by the arrow keys.
+const CAMERA_PAN_SPEED: f32 = 10.0;
+// Default gravity pulling bunnies down each tick. Now a starting value for the
+// runtime-tunable `Tuning::gravity` so the imgui slider can override it.
+const DEFAULT_GRAVITY: f32 = 0.5;
+// Default vertical restitution applied on a floor bounce (negative = rebound).
+const DEFAULT_DAMPING: f32 = -0.8;
+
+// How fast (pixels per tick) the left analog stick drives the virtual cursor.
+const GAMEPAD_CURSOR_SPEED: f32 = 8.0;
+// Stick magnitudes below this are treated as zero to absorb resting drift.
+const GAMEPAD_DEADZONE: f32 = 0.15;
+
+// Size (in pixels) of the radial falloff sprite used for each light source.
+const SPOT_SIZE: u16 = 256;
+// How many of the most recently spawned bunnies act as light sources.
+const BUNNY_LIGHTS: usize = 8;
+
+/// Builds the radial falloff sprite used for every light: a white center that
+/// fades smoothly to fully transparent at the edges. Drawn additively into the
+/// lightmap it accumulates into soft pools of light.
+fn make_spot(ctx: &mut Context) -> ggez::GameResult<Image> {
+    let size = SPOT_SIZE as usize;
+    let radius = size as f32 / 2.0;
+    let mut pixels = Vec::with_capacity(size * size * 4);
+    for y in 0..size {
+        for x in 0..size {
+            let dx = x as f32 + 0.5 - radius;
+            let dy = y as f32 + 0.5 - radius;
+            let dist = (dx * dx + dy * dy).sqrt();
+            let falloff = (1.0 - dist / radius).max(0.0);
+            // Square the falloff for a softer, rounder pool of light.
+            let alpha = (falloff * falloff * 255.0) as u8;
+            pixels.extend_from_slice(&[255, 255, 255, alpha]);
+        }
+    }
+
+    Image::from_rgba8(ctx, SPOT_SIZE, SPOT_SIZE, &pixels)
+}
+
+/// Builds a small solid-color square used as a playback-control button.
+fn make_button(ctx: &mut Context, (r, g, b): (f32, f32, f32)) -> ggez::GameResult<Image> {
+    let size = BUTTON_SIZE as usize;
+    let (r, g, b) = ((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8);
+    let mut pixels = Vec::with_capacity(size * size * 4);
+    for _ in 0..size * size {
+        pixels.extend_from_slice(&[r, g, b, 255]);
+    }
+    Image::from_rgba8(ctx, BUTTON_SIZE as u16, BUTTON_SIZE as u16, &pixels)
+}
+
+/// Screen-space top-left corner of playback button `index` (0 = paused).
+fn button_origin(index: usize) -> na::Point2<f32> {
+    let x = BUTTON_MARGIN + index as f32 * (BUTTON_SIZE + BUTTON_MARGIN);
+    na::Point2::new(x, BUTTON_MARGIN)
+}
+
+// How many integration steps a single `check_update_time` tick runs in fast
+// mode, speeding the swarm up without touching `DESIRED_FPS`.
+const FAST_FORWARD_STEPS: u32 = 4;
+// Size and spacing of the on-screen playback buttons.
+const BUTTON_SIZE: f32 = 32.0;
+const BUTTON_MARGIN: f32 = 8.0;
+
+/// What an active touch does, decided by the screen half it started in: the
+/// left region spawns bunnies (flinging along the finger's path), the right
+/// region deletes them.
+#[derive(Copy, Clone, PartialEq)]
+enum TouchKind {
+    Spawn,
+    Delete,
+}
+
+/// Playback speed for the fixed-timestep loop.
+#[derive(Copy, Clone, PartialEq)]
+enum SimSpeed {
+    Paused,
+    Normal,
+    Fast,
+}
+
+impl SimSpeed {
+    /// Number of integration steps to run per `check_update_time` tick.
+    fn steps(self) -> u32 {
+        match self {
+            SimSpeed::Paused => 0,
+            SimSpeed::Normal => 1,
+            SimSpeed::Fast => FAST_FORWARD_STEPS,
+        }
+    }
+
+    /// Short label shown in the window title.
+    fn label(self) -> &'static str {
+        match self {
+            SimSpeed::Paused => "paused",
+            SimSpeed::Normal => "1x",
+            SimSpeed::Fast => "fast",
+        }
+    }
+}
+
+/// Top-left offset of the viewport within the (larger) world. Subtracted from
+/// world positions when drawing so the camera can scroll across the swarm.
+struct Frame {
+    x: f32,
+    y: f32,
+}
 
 struct Bunny {
     position: na::Point2<f32>,
@@ -47,14 +164,37 @@ struct GameState {
     bunnies: Vec<Bunny>,
     max_x: f32,
     max_y: f32,
+    frame: Frame,
 
     delete_held: bool,
     add_held: bool,
     ctrl_held: bool,
 
+    // Gamepad: a virtual cursor moved by the left stick, plus trigger-driven
+    // spawn/delete so the demo is playable without a mouse.
+    virtual_cursor: na::Point2<f32>,
+    stick: na::Vector2<f32>,
+    pad_add_held: bool,
+    pad_delete_held: bool,
+
     click_timer: i32,
     bunnybatch: SpriteBatch,
-    batched_drawing: bool,
+
+    tuning: Tuning,
+    imgui_wrapper: ImGuiWrapper,
+    fps_history: Vec<f32>,
+
+    spot: Image,
+    lightmap: Canvas,
+    lights_enabled: bool,
+
+    sim_speed: SimSpeed,
+    // Small corner buttons for paused / 1x / fast, in that order.
+    buttons: [Image; 3],
+
+    // Active touches keyed by finger id, so multiple fingers can spawn and
+    // delete at once.
+    touches: HashMap<u64, TouchKind>,
 }
 
 impl GameState {
@@ -67,8 +207,10 @@ impl GameState {
 
         let texture = Image::from_rgba8(ctx, width as u16, height as u16, &img)?;
         let mut bunnies = Vec::with_capacity(INITIAL_BUNNIES);
-        let max_x = (WIDTH - texture.width()) as f32;
-        let max_y = (HEIGHT - texture.height()) as f32;
+        // Bounds track the world, not the window, so the swarm can spill far
+        // beyond the visible viewport.
+        let max_x = WORLD_WIDTH - texture.width() as f32;
+        let max_y = WORLD_HEIGHT - texture.height() as f32;
 
         for _ in 0..INITIAL_BUNNIES {
             bunnies.push(Bunny::new(0.0, 0.0, &mut rng));
@@ -76,20 +218,55 @@ impl GameState {
 
         let bunnybatch = SpriteBatch::new(texture.clone());
 
+        let spot = make_spot(ctx)?;
+        let lightmap = Canvas::with_window_size(ctx)?;
+
+        let buttons = [
+            make_button(ctx, (0.8, 0.3, 0.3))?,
+            make_button(ctx, (0.3, 0.8, 0.3))?,
+            make_button(ctx, (0.3, 0.5, 0.9))?,
+        ];
+
+        let tuning = Tuning {
+            gravity: DEFAULT_GRAVITY,
+            damping: DEFAULT_DAMPING,
+            spawn_burst: INITIAL_BUNNIES as i32,
+            batched_drawing: true,
+        };
+        let imgui_wrapper = ImGuiWrapper::new(ctx);
+
         Ok(GameState {
             rng,
             texture,
             bunnies,
             max_x,
             max_y,
+            frame: Frame { x: 0.0, y: 0.0 },
 
             delete_held: false,
             add_held: false,
             ctrl_held: false,
 
+            virtual_cursor: na::Point2::new(WIDTH as f32 / 2.0, HEIGHT as f32 / 2.0),
+            stick: na::Vector2::new(0.0, 0.0),
+            pad_add_held: false,
+            pad_delete_held: false,
+
             click_timer: 0,
             bunnybatch,
-            batched_drawing: true,
+
+            tuning,
+            imgui_wrapper,
+            fps_history: Vec::new(),
+
+            spot,
+            lightmap,
+            lights_enabled: false,
+
+            sim_speed: SimSpeed::Normal,
+            buttons,
+
+            touches: HashMap::new(),
         })
     }
 }
@@ -103,17 +280,44 @@ impl event::EventHandler for GameState {
                 self.click_timer -= 1;
             }
 
+            // Drive the virtual cursor from the left stick. Clamp the raw vector
+            // to a unit vector first so diagonal input isn't faster than
+            // cardinal, and ignore tiny magnitudes inside the deadzone.
+            let mut dir = self.stick;
+            let magnitude = (dir.x * dir.x + dir.y * dir.y).sqrt();
+            if magnitude < GAMEPAD_DEADZONE {
+                dir = na::Vector2::new(0.0, 0.0);
+            } else if magnitude > 1.0 {
+                dir /= magnitude;
+            }
+            self.virtual_cursor += dir * GAMEPAD_CURSOR_SPEED;
+            self.virtual_cursor.x = self.virtual_cursor.x.max(0.0).min(self.max_x);
+            self.virtual_cursor.y = self.virtual_cursor.y.max(0.0).min(self.max_y);
+
+            if self.pad_add_held {
+                let pos = self.virtual_cursor;
+                self.bunnies.push(Bunny::new(pos.x, pos.y, &mut self.rng));
+            }
+            if self.pad_delete_held && !self.bunnies.is_empty() {
+                let index = self.rng.gen_range(0, self.bunnies.len());
+                self.bunnies.remove(index);
+            }
+
             if self.add_held {
                 if self.ctrl_held {
                     let (width, _) = ggez::graphics::size(ctx);
-                    for _ in 0..INITIAL_BUNNIES {
+                    for _ in 0..self.tuning.spawn_burst {
                         let width = self.rng.gen_range(0.0, width);
                         self.bunnies.push(Bunny::new(width, 0.0, &mut self.rng));
                     }
                 } else {
+                    // The mouse is in screen space; shift into world space so
+                    // bunnies spawn under the cursor regardless of scroll.
                     let pos = position(ctx);
+                    let x = pos.x + self.frame.x;
+                    let y = pos.y + self.frame.y;
 
-                    self.bunnies.push(Bunny::new(pos.x, pos.y, &mut self.rng));
+                    self.bunnies.push(Bunny::new(x, y, &mut self.rng));
                 }
             }
 
@@ -137,64 +341,232 @@ impl event::EventHandler for GameState {
                 }
             }
 
-            for bunny in &mut self.bunnies {
-                bunny.position += bunny.velocity;
-                bunny.velocity.y += GRAVITY;
+            // Advance physics according to the playback speed: paused skips it
+            // entirely (input above is still serviced), fast runs it several
+            // times per tick.
+            for _ in 0..self.sim_speed.steps() {
+                self.integrate();
+            }
+
+            self.update_camera(ctx);
+        }
 
-                if bunny.position.x > self.max_x {
-                    bunny.velocity.x *= -1.0;
-                    bunny.position.x = self.max_x;
-                } else if bunny.position.x < 0.0 {
-                    bunny.velocity.x *= -1.0;
-                    bunny.position.x = 0.0;
-                }
+        Ok(())
+    }
 
-                if bunny.position.y > self.max_y {
-                    bunny.velocity.y *= -0.8;
-                    bunny.position.y = self.max_y;
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        self.draw_scene(ctx)
+    }
+}
 
-                    if self.rng.gen::<bool>() {
-                        bunny.velocity.y -= 3.0 + (self.rng.gen::<f32>() * 4.0);
-                    }
-                } else if bunny.position.y < 0.0 {
-                    bunny.velocity.y = 0.0;
-                    bunny.position.y = 0.0;
+impl GameState {
+    /// Integrates every bunny one physics step: gravity, wall bounces, and the
+    /// occasional floor-bounce kick.
+    fn integrate(&mut self) {
+        let gravity = self.tuning.gravity;
+        let damping = self.tuning.damping;
+        for bunny in &mut self.bunnies {
+            bunny.position += bunny.velocity;
+            bunny.velocity.y += gravity;
+
+            if bunny.position.x > self.max_x {
+                bunny.velocity.x *= -1.0;
+                bunny.position.x = self.max_x;
+            } else if bunny.position.x < 0.0 {
+                bunny.velocity.x *= -1.0;
+                bunny.position.x = 0.0;
+            }
+
+            if bunny.position.y > self.max_y {
+                bunny.velocity.y *= damping;
+                bunny.position.y = self.max_y;
+
+                if self.rng.gen::<bool>() {
+                    bunny.velocity.y -= 3.0 + (self.rng.gen::<f32>() * 4.0);
                 }
+            } else if bunny.position.y < 0.0 {
+                bunny.velocity.y = 0.0;
+                bunny.position.y = 0.0;
             }
         }
+    }
 
-        Ok(())
+    /// Moves the viewport each tick. Arrow keys pan manually; otherwise the
+    /// camera follows the centroid of all bunnies. Either way the frame is
+    /// clamped so it never scrolls past a world edge, and axes smaller than the
+    /// viewport are centered instead.
+    fn update_camera(&mut self, ctx: &mut Context) {
+        use input::keyboard::{is_key_pressed, KeyCode};
+
+        let (view_w, view_h) = ggez::graphics::size(ctx);
+
+        let left = is_key_pressed(ctx, KeyCode::Left);
+        let right = is_key_pressed(ctx, KeyCode::Right);
+        let up = is_key_pressed(ctx, KeyCode::Up);
+        let down = is_key_pressed(ctx, KeyCode::Down);
+
+        if left || right || up || down {
+            if left {
+                self.frame.x -= CAMERA_PAN_SPEED;
+            }
+            if right {
+                self.frame.x += CAMERA_PAN_SPEED;
+            }
+            if up {
+                self.frame.y -= CAMERA_PAN_SPEED;
+            }
+            if down {
+                self.frame.y += CAMERA_PAN_SPEED;
+            }
+        } else if !self.bunnies.is_empty() {
+            let mut cx = 0.0;
+            let mut cy = 0.0;
+            for bunny in &self.bunnies {
+                cx += bunny.position.x;
+                cy += bunny.position.y;
+            }
+            let count = self.bunnies.len() as f32;
+            self.frame.x = cx / count - view_w / 2.0;
+            self.frame.y = cy / count - view_h / 2.0;
+        }
+
+        self.frame.x = clamp_axis(self.frame.x, WORLD_WIDTH, view_w);
+        self.frame.y = clamp_axis(self.frame.y, WORLD_HEIGHT, view_h);
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
         graphics::clear(ctx, Color::from((0.392, 0.584, 0.929)));
 
-        if self.batched_drawing {
+        // Translate everything by the camera frame so world-space positions map
+        // to the visible viewport.
+        let offset = na::Vector2::new(-self.frame.x, -self.frame.y);
+        if self.tuning.batched_drawing {
             self.bunnybatch.clear();
             for bunny in &self.bunnies {
                 self.bunnybatch.add((bunny.position,));
             }
-            graphics::draw(ctx, &self.bunnybatch, (na::Point2::new(0.0, 0.0),))?;
+            graphics::draw(ctx, &self.bunnybatch, (na::Point2::new(offset.x, offset.y),))?;
         } else {
             for bunny in &self.bunnies {
-                graphics::draw(ctx, &self.texture, (bunny.position,))?;
+                graphics::draw(ctx, &self.texture, (bunny.position + offset,))?;
             }
         }
 
+        if self.lights_enabled {
+            self.draw_lighting(ctx)?;
+        }
+
+        // Playback buttons live in a screen-space corner; the active one is
+        // drawn fully opaque, the others dimmed.
+        let speeds = [SimSpeed::Paused, SimSpeed::Normal, SimSpeed::Fast];
+        for (i, button) in self.buttons.iter().enumerate() {
+            let alpha = if self.sim_speed == speeds[i] { 1.0 } else { 0.4 };
+            graphics::draw(
+                ctx,
+                button,
+                DrawParam::new()
+                    .dest(button_origin(i))
+                    .color(Color::new(1.0, 1.0, 1.0, alpha)),
+            )?;
+        }
+
         graphics::set_window_title(
             ctx,
             &format!(
-                "BunnyMark - {} bunnies - {:.0} FPS - batched drawing: {}",
+                "BunnyMark - {} bunnies - {:.0} FPS - batched drawing: {} - {}",
                 self.bunnies.len(),
                 timer::fps(ctx),
-                self.batched_drawing
+                self.tuning.batched_drawing,
+                self.sim_speed.label()
             ),
         );
+
+        // Keep a rolling window of recent framerates for the imgui plot.
+        self.fps_history.push(timer::fps(ctx) as f32);
+        if self.fps_history.len() > 90 {
+            self.fps_history.remove(0);
+        }
+
+        let hidpi_factor = graphics::hidpi_factor(ctx);
+        let bunny_count = self.bunnies.len();
+        self.imgui_wrapper.render(
+            ctx,
+            hidpi_factor,
+            &mut self.tuning,
+            &self.fps_history,
+            bunny_count,
+        );
+
         graphics::present(ctx)?;
 
         Ok(())
     }
 
+    /// Composites dynamic lighting on top of the already-drawn scene.
+    ///
+    /// Each light's falloff sprite is accumulated additively into an offscreen
+    /// black canvas, which is then multiplied over the screen so unlit areas
+    /// darken. The canvas tracks the window size (see `resize_event`) so the
+    /// multiply stays pixel-aligned with the scene. Every pass restores
+    /// `BlendMode::Alpha` before returning.
+    fn draw_lighting(&mut self, ctx: &mut Context) -> GameResult {
+        let spot_size = SPOT_SIZE as f32;
+
+        graphics::set_canvas(ctx, Some(&self.lightmap));
+        graphics::clear(ctx, Color::from((0.0, 0.0, 0.0)));
+        graphics::set_blend_mode(ctx, BlendMode::Add)?;
+
+        for light in self.lights(ctx) {
+            let dest = na::Point2::new(light.x - spot_size / 2.0, light.y - spot_size / 2.0);
+            graphics::draw(ctx, &self.spot, DrawParam::new().dest(dest))?;
+        }
+
+        graphics::set_blend_mode(ctx, BlendMode::Alpha)?;
+        graphics::set_canvas(ctx, None);
+
+        graphics::set_blend_mode(ctx, BlendMode::Multiply)?;
+        graphics::draw(ctx, &self.lightmap, DrawParam::new())?;
+        graphics::set_blend_mode(ctx, BlendMode::Alpha)?;
+
+        Ok(())
+    }
+
+    /// Acts on a touch point, mirroring the mouse add/delete behavior: spawn a
+    /// bunny at the touch (shifted into world space) or remove a random one.
+    fn apply_touch(&mut self, kind: TouchKind, x: f32, y: f32) {
+        match kind {
+            TouchKind::Spawn => {
+                let wx = x + self.frame.x;
+                let wy = y + self.frame.y;
+                self.bunnies.push(Bunny::new(wx, wy, &mut self.rng));
+            }
+            TouchKind::Delete => {
+                if !self.bunnies.is_empty() {
+                    let index = self.rng.gen_range(0, self.bunnies.len());
+                    self.bunnies.remove(index);
+                }
+            }
+        }
+    }
+
+    /// Gathers the active light positions: the mouse cursor plus the most
+    /// recently spawned bunnies.
+    fn lights(&self, ctx: &Context) -> Vec<na::Point2<f32>> {
+        let cursor = position(ctx);
+        let mut lights = vec![na::Point2::new(cursor.x, cursor.y)];
+        // Bunny positions are world-space; shift into viewport space to match
+        // the screen-space lightmap.
+        for bunny in self.bunnies.iter().rev().take(BUNNY_LIGHTS) {
+            lights.push(na::Point2::new(
+                bunny.position.x - self.frame.x,
+                bunny.position.y - self.frame.y,
+            ));
+        }
+        lights
+    }
+}
+
+impl event::EventHandler for GameState {
     fn key_down_event(
         &mut self,
         ctx: &mut Context,
@@ -203,11 +575,27 @@ impl event::EventHandler for GameState {
         _repeat: bool,
     ) {
         if keycode == KeyCode::Space {
-            self.batched_drawing = !self.batched_drawing;
+            self.tuning.batched_drawing = !self.tuning.batched_drawing;
         }
         if keycode == KeyCode::Back {
             self.bunnies.clear();
         }
+        if keycode == KeyCode::L {
+            self.lights_enabled = !self.lights_enabled;
+        }
+        if keycode == KeyCode::P {
+            self.sim_speed = if self.sim_speed == SimSpeed::Paused {
+                SimSpeed::Normal
+            } else {
+                SimSpeed::Paused
+            };
+        }
+        if keycode == KeyCode::Key1 {
+            self.sim_speed = SimSpeed::Normal;
+        }
+        if keycode == KeyCode::Key2 {
+            self.sim_speed = SimSpeed::Fast;
+        }
         if keycode == KeyCode::Escape {
             quit(ctx);
         }
@@ -223,13 +611,79 @@ impl event::EventHandler for GameState {
         }
     }
 
+    fn resize_event(&mut self, ctx: &mut Context, width: f32, height: f32) {
+        // Keep the viewport coordinate space matched to the new window size...
+        let rect = graphics::Rect::new(0.0, 0.0, width, height);
+        let _ = graphics::set_screen_coordinates(ctx, rect);
+        // ...and reallocate the lightmap, or the multiply pass would be
+        // misaligned with the scene.
+        if let Ok(canvas) = Canvas::with_window_size(ctx) {
+            self.lightmap = canvas;
+        }
+    }
+
+    fn gamepad_axis_event(&mut self, _ctx: &mut Context, axis: Axis, value: f32, _id: GamepadId) {
+        // Stick Y is positive-up on the pad but positive-down on screen.
+        match axis {
+            Axis::LeftStickX => self.stick.x = value,
+            Axis::LeftStickY => self.stick.y = -value,
+            _ => {}
+        }
+    }
+
+    fn gamepad_button_down_event(&mut self, _ctx: &mut Context, btn: Button, _id: GamepadId) {
+        match btn {
+            Button::RightTrigger2 => self.pad_add_held = true,
+            Button::LeftTrigger2 => self.pad_delete_held = true,
+            Button::South => self.tuning.batched_drawing = !self.tuning.batched_drawing,
+            _ => {}
+        }
+    }
+
+    fn gamepad_button_up_event(&mut self, _ctx: &mut Context, btn: Button, _id: GamepadId) {
+        match btn {
+            Button::RightTrigger2 => self.pad_add_held = false,
+            Button::LeftTrigger2 => self.pad_delete_held = false,
+            _ => {}
+        }
+    }
+
+    fn mouse_motion_event(&mut self, _ctx: &mut Context, x: f32, y: f32, _dx: f32, _dy: f32) {
+        self.imgui_wrapper.update_mouse_pos(x, y);
+    }
+
     fn mouse_button_down_event(
         &mut self,
         _ctx: &mut Context,
         button: input::mouse::MouseButton,
-        _x: f32,
-        _y: f32,
+        x: f32,
+        y: f32,
     ) {
+        self.imgui_wrapper
+            .update_mouse_down(mouse_button_tuple(button));
+
+        // Clicks that land on the overlay belong to imgui, not the swarm.
+        if self.imgui_wrapper.wants_mouse() {
+            return;
+        }
+
+        // A left click on a playback button selects that speed instead of
+        // spawning bunnies.
+        if button == input::mouse::MouseButton::Left {
+            let speeds = [SimSpeed::Paused, SimSpeed::Normal, SimSpeed::Fast];
+            for (i, speed) in speeds.iter().enumerate() {
+                let origin = button_origin(i);
+                if x >= origin.x
+                    && x < origin.x + BUTTON_SIZE
+                    && y >= origin.y
+                    && y < origin.y + BUTTON_SIZE
+                {
+                    self.sim_speed = *speed;
+                    return;
+                }
+            }
+        }
+
         if button == input::mouse::MouseButton::Left {
             self.add_held = true;
         }
@@ -246,6 +700,8 @@ impl event::EventHandler for GameState {
         _x: f32,
         _y: f32,
     ) {
+        self.imgui_wrapper.update_mouse_down((false, false, false));
+
         if button == input::mouse::MouseButton::Right {
             self.delete_held = false;
         }
@@ -253,6 +709,52 @@ impl event::EventHandler for GameState {
             self.add_held = false;
         }
     }
+
+    fn touch_event(&mut self, ctx: &mut Context, phase: TouchPhase, x: f64, y: f64, id: u64) {
+        let (x, y) = (x as f32, y as f32);
+        match phase {
+            TouchPhase::Started => {
+                let (width, _) = ggez::graphics::size(ctx);
+                let kind = if x < width / 2.0 {
+                    TouchKind::Spawn
+                } else {
+                    TouchKind::Delete
+                };
+                self.touches.insert(id, kind);
+                self.apply_touch(kind, x, y);
+            }
+            // Track the moving finger so a drag flings a trail of bunnies.
+            TouchPhase::Moved => {
+                if let Some(&kind) = self.touches.get(&id) {
+                    self.apply_touch(kind, x, y);
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.touches.remove(&id);
+            }
+        }
+    }
+}
+
+/// Clamps one camera axis so the viewport stays inside the world. When the
+/// world is smaller than the viewport on that axis the world is centered
+/// instead (yielding a negative offset).
+fn clamp_axis(offset: f32, world: f32, viewport: f32) -> f32 {
+    if world < viewport {
+        -(viewport - world) / 2.0
+    } else {
+        offset.max(0.0).min(world - viewport)
+    }
+}
+
+/// Maps a ggez mouse button to the `(left, middle, right)` tuple imgui expects.
+fn mouse_button_tuple(button: input::mouse::MouseButton) -> (bool, bool, bool) {
+    use input::mouse::MouseButton;
+    (
+        button == MouseButton::Left,
+        button == MouseButton::Middle,
+        button == MouseButton::Right,
+    )
 }
 
 fn main() -> GameResult {
@@ -270,6 +772,15 @@ fn main() -> GameResult {
             vsync: false,
             ..Default::default()
         });
+
+    // On Android the activity owns the surface, so let it pick the window
+    // dimensions instead of forcing the desktop default.
+    #[cfg(target_os = "android")]
+    let cb = cb.window_mode(conf::WindowMode {
+        fullscreen_type: conf::FullscreenType::True,
+        ..Default::default()
+    });
+
     let (ctx, event_loop) = &mut cb.build()?;
 
     let state = &mut GameState::new(ctx)?;